@@ -0,0 +1,58 @@
+use std::fs;
+use std::path::PathBuf;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use sha2::{Digest, Sha512};
+
+use crate::dirs::config;
+
+/// An artifact that can be read back from and written to the on-disk cache,
+/// keyed by the SHA-512 hash of the source it was compiled from.
+///
+/// Parsing and resolving a `.syncat` file with tree-sitter is expensive, and
+/// syncat is typically run once per file in a pipeline, so the resolved result
+/// is serialized under `config()/cache` and re-read whenever the source hash is
+/// unchanged.
+pub(crate) trait Cached: Serialize + DeserializeOwned {
+    /// The subdirectory of the cache this kind of artifact is stored in.
+    const NAMESPACE: &'static str;
+
+    fn cache_dir() -> PathBuf {
+        config().join("cache").join(Self::NAMESPACE)
+    }
+
+    /// Read a previously cached artifact for `key`, or `None` on a miss (or if
+    /// the cache entry is unreadable or stale in format).
+    fn load_cached(key: &str) -> Option<Self> {
+        let bytes = fs::read(Self::cache_dir().join(key)).ok()?;
+        bincode::deserialize(&bytes).ok()
+    }
+
+    /// Write this artifact to the cache under `key`. Failures are swallowed: a
+    /// cache that cannot be written is a missed optimisation, not an error.
+    fn store_cached(&self, key: &str) {
+        let dir = Self::cache_dir();
+        if fs::create_dir_all(&dir).is_err() {
+            return;
+        }
+        if let Ok(bytes) = bincode::serialize(self) {
+            let _ = fs::write(dir.join(key), bytes);
+        }
+    }
+}
+
+/// Compute the cache key for a stylesheet: the SHA-512 of its own source folded
+/// with the source of every transitively `@import`-ed file, so that editing an
+/// imported palette invalidates every dependent.
+pub(crate) fn content_hash(source: &str, imports: &[PathBuf]) -> String {
+    let mut hasher = Sha512::new();
+    hasher.update(source.as_bytes());
+    for path in imports {
+        if let Ok(bytes) = fs::read(path) {
+            hasher.update(path.to_string_lossy().as_bytes());
+            hasher.update(&bytes);
+        }
+    }
+    format!("{:x}", hasher.finalize())
+}
@@ -1,12 +1,17 @@
 use std::fs;
+use std::path::Path;
 use tree_sitter::Parser;
 use ansi_term::ANSIGenericString;
 
+use serde::{Serialize, Deserialize};
+
 use crate::language::Lang;
 use crate::dirs::config;
+use crate::cache::{Cached, content_hash};
 use crate::stylesheet::{Stylesheet, Context};
 use crate::style::{setting, StyleBuilder, Colour};
 
+#[derive(Serialize, Deserialize)]
 pub struct MetaStylesheet {
     pub line_ending: StyleBuilder,
     pub line_number: StyleBuilder,
@@ -53,6 +58,10 @@ impl Default for MetaStylesheet {
     }
 }
 
+impl Cached for MetaStylesheet {
+    const NAMESPACE: &'static str = "meta";
+}
+
 impl MetaStylesheet {
     pub fn margin(&self) -> ANSIGenericString<str> {
         self.margin
@@ -92,19 +101,71 @@ impl MetaStylesheet {
 }
 
 pub fn load_meta_stylesheet() -> MetaStylesheet {
-    let stylesheet = {
-        let style_file = config().join("style/active/.syncat");
-        if !style_file.exists() {
-            Stylesheet::default()
-        } else {
-            let style_def = fs::read_to_string(&style_file).map_err(Box::new).expect(&format!("Cannot read meta stylesheet {:?}", style_file));
-            let mut parser = Parser::new();
-            parser.set_language(Lang::Syncat.parser()).unwrap();
-            let tree = parser.parse(&style_def, None).expect(&format!("Could not parse stylesheet at file {:?}", &style_file));
-            Stylesheet::parse(&style_def, tree).expect(&format!("Meta stylesheet {:?} is invalid", style_file))
-        }
+    let style_file = config().join("style/active/.syncat");
+    if !style_file.exists() {
+        return build_meta_stylesheet(&Stylesheet::default());
+    }
+
+    let style_def = fs::read_to_string(&style_file).map_err(Box::new).expect(&format!("Cannot read meta stylesheet {:?}", style_file));
+    let mut parser = Parser::new();
+    parser.set_language(Lang::Syncat.parser()).unwrap();
+    let tree = parser.parse(&style_def, None).expect(&format!("Could not parse stylesheet at file {:?}", &style_file));
+    let stylesheet = Stylesheet::parse(&style_def, tree).expect(&format!("Meta stylesheet {:?} is invalid", style_file));
+
+    // Key the cache on the source folded with every file it transitively
+    // imports, so that editing any palette in the import graph invalidates the
+    // cached result. Imports are declared relative to the importing file, so
+    // each is resolved against its own directory as the graph is walked.
+    let import_dir = style_file.parent().unwrap_or_else(|| Path::new("."));
+    let mut visited = Vec::new();
+    let mut imports = Vec::new();
+    for import in stylesheet.imports() {
+        collect_imports(&import_dir.join(import), &mut visited, &mut imports);
+    }
+    let key = content_hash(&style_def, &imports);
+    if let Some(meta_stylesheet) = MetaStylesheet::load_cached(&key) {
+        return meta_stylesheet;
+    }
+    let meta_stylesheet = build_meta_stylesheet(&stylesheet);
+    meta_stylesheet.store_cached(&key);
+    meta_stylesheet
+}
+
+/// Walk the `@import` graph rooted at `path`, appending each reachable file to
+/// `out` in visit order. `visited` holds the canonical paths already seen so a
+/// file imported more than once — or an import cycle — is folded in only once.
+fn collect_imports(path: &Path, visited: &mut Vec<std::path::PathBuf>, out: &mut Vec<std::path::PathBuf>) {
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    if visited.contains(&canonical) {
+        return;
+    }
+    visited.push(canonical);
+    out.push(path.to_path_buf());
+
+    let source = match fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(..) => return,
+    };
+    let stylesheet = match parse_stylesheet(&source) {
+        Some(stylesheet) => stylesheet,
+        None => return,
     };
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    for import in stylesheet.imports() {
+        collect_imports(&dir.join(import), visited, out);
+    }
+}
+
+/// Parse a `.syncat` source into a `Stylesheet`, or `None` if it cannot be
+/// read as one. Used to discover the imports of an imported file.
+fn parse_stylesheet(source: &str) -> Option<Stylesheet> {
+    let mut parser = Parser::new();
+    parser.set_language(Lang::Syncat.parser()).ok()?;
+    let tree = parser.parse(source, None)?;
+    Stylesheet::parse(source, tree).ok()
+}
 
+fn build_meta_stylesheet(stylesheet: &Stylesheet) -> MetaStylesheet {
     let mut meta_stylesheet = MetaStylesheet::default();
     meta_stylesheet.line_ending = meta_stylesheet.line_ending
         .merge_with(&stylesheet.resolve(&Context::default(), &[("line_ending", 0)], None));
@@ -0,0 +1,150 @@
+use crate::style::Colour;
+
+/// The 16 standard ANSI colours, approximated as RGB so the transform
+/// functions have something concrete to work with.
+const SYSTEM: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// The six levels each channel takes in the xterm 6×6×6 colour cube.
+const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn clamp_percent(percent: f32) -> f32 {
+    percent.max(0.0).min(100.0)
+}
+
+/// Map any `Colour` onto its nearest RGB approximation. Named and 256-indexed
+/// colours are looked up; `RGB` is passed through untouched.
+fn rgb(colour: &Colour) -> (u8, u8, u8) {
+    match colour {
+        Colour::Black => SYSTEM[0],
+        Colour::Red => SYSTEM[1],
+        Colour::Green => SYSTEM[2],
+        Colour::Yellow => SYSTEM[3],
+        Colour::Blue => SYSTEM[4],
+        Colour::Purple => SYSTEM[5],
+        Colour::Cyan => SYSTEM[6],
+        Colour::White => SYSTEM[7],
+        Colour::Fixed(index) => fixed_rgb(*index),
+        Colour::RGB(r, g, b) => (*r, *g, *b),
+    }
+}
+
+fn fixed_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => SYSTEM[index as usize],
+        16..=231 => {
+            let index = index - 16;
+            (
+                CUBE[(index / 36) as usize],
+                CUBE[((index / 6) % 6) as usize],
+                CUBE[(index % 6) as usize],
+            )
+        }
+        _ => {
+            let level = 8 + 10 * (index - 232);
+            (level, level, level)
+        }
+    }
+}
+
+fn to_hsl((r, g, b): (u8, u8, u8)) -> (f32, f32, f32) {
+    let r = r as f32 / 255.0;
+    let g = g as f32 / 255.0;
+    let b = b as f32 / 255.0;
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let lightness = (max + min) / 2.0;
+    if (max - min).abs() < f32::EPSILON {
+        return (0.0, 0.0, lightness);
+    }
+    let delta = max - min;
+    let saturation = if lightness > 0.5 {
+        delta / (2.0 - max - min)
+    } else {
+        delta / (max + min)
+    };
+    let hue = if (max - r).abs() < f32::EPSILON {
+        (g - b) / delta + if g < b { 6.0 } else { 0.0 }
+    } else if (max - g).abs() < f32::EPSILON {
+        (b - r) / delta + 2.0
+    } else {
+        (r - g) / delta + 4.0
+    };
+    (hue / 6.0, saturation, lightness)
+}
+
+fn hue_channel(p: f32, q: f32, mut t: f32) -> f32 {
+    if t < 0.0 {
+        t += 1.0;
+    }
+    if t > 1.0 {
+        t -= 1.0;
+    }
+    if t < 1.0 / 6.0 {
+        p + (q - p) * 6.0 * t
+    } else if t < 1.0 / 2.0 {
+        q
+    } else if t < 2.0 / 3.0 {
+        p + (q - p) * (2.0 / 3.0 - t) * 6.0
+    } else {
+        p
+    }
+}
+
+fn from_hsl((h, s, l): (f32, f32, f32)) -> Colour {
+    if s.abs() < f32::EPSILON {
+        let value = (l * 255.0).round() as u8;
+        return Colour::RGB(value, value, value);
+    }
+    let q = if l < 0.5 { l * (1.0 + s) } else { l + s - l * s };
+    let p = 2.0 * l - q;
+    let channel = |t: f32| (hue_channel(p, q, t) * 255.0).round() as u8;
+    Colour::RGB(
+        channel(h + 1.0 / 3.0),
+        channel(h),
+        channel(h - 1.0 / 3.0),
+    )
+}
+
+/// Lighten a colour by `percent` percentage points of lightness.
+pub(crate) fn lighten(colour: &Colour, percent: f32) -> Colour {
+    let (h, s, l) = to_hsl(rgb(colour));
+    from_hsl((h, s, (l + clamp_percent(percent) / 100.0).min(1.0)))
+}
+
+/// Darken a colour by `percent` percentage points of lightness.
+pub(crate) fn darken(colour: &Colour, percent: f32) -> Colour {
+    let (h, s, l) = to_hsl(rgb(colour));
+    from_hsl((h, s, (l - clamp_percent(percent) / 100.0).max(0.0)))
+}
+
+/// Blend `a` into `b`, `weight`% of the way towards `a`, component-wise.
+pub(crate) fn mix(a: &Colour, b: &Colour, weight: f32) -> Colour {
+    let weight = clamp_percent(weight) / 100.0;
+    let (ar, ag, ab) = rgb(a);
+    let (br, bg, bb) = rgb(b);
+    let blend = |a: u8, b: u8| (a as f32 * weight + b as f32 * (1.0 - weight)).round() as u8;
+    Colour::RGB(blend(ar, br), blend(ag, bg), blend(ab, bb))
+}
+
+/// Approximate transparency by blending `colour` onto `background` at the given
+/// opacity, since the terminal cannot render true alpha.
+pub(crate) fn alpha(colour: &Colour, alpha: f32, background: &Colour) -> Colour {
+    mix(colour, background, alpha)
+}
@@ -1,7 +1,9 @@
 use std::collections::BTreeMap;
+use std::path::PathBuf;
 
 use tree_sitter::{Tree, Node};
 use regex::Regex;
+use serde::{Serialize, Deserialize};
 
 use crate::error::Error;
 use crate::style::{Colour, StyleBuilder};
@@ -9,10 +11,14 @@ use crate::language::Lang;
 
 mod resolver;
 mod parser;
+mod variables;
+mod color;
+mod tmtheme;
 
 pub use resolver::Context;
+pub(crate) use variables::{Value, Variables};
 
-#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Serialize, Deserialize)]
 enum SelectorSegment {
     Kind(String),
     Token(String),
@@ -22,8 +28,87 @@ enum SelectorSegment {
     BranchCheck(Vec<SelectorSegment>),
 }
 
-#[derive(Default, Debug)]
+/// A chain of node kinds naming a top-level selector, e.g. `["function_definition"]`.
+/// Used as the target of an `@extend`.
+type SelectorPath = Vec<String>;
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct Stylesheet {
     style: StyleBuilder,
     scopes: BTreeMap<SelectorSegment, Stylesheet>,
+    extends: Vec<SelectorPath>,
+    #[serde(default)]
+    imports: Vec<PathBuf>,
+}
+
+impl Stylesheet {
+    /// The paths this stylesheet's own `@import`s declare, as written and in
+    /// declaration order; imports of imported files are not included. Used to
+    /// key the compiled cache so an edit to an imported file invalidates
+    /// dependents.
+    pub(crate) fn imports(&self) -> Vec<PathBuf> {
+        self.imports.clone()
+    }
+
+    /// Insert a parsed block under each selector of a comma-separated list, as
+    /// written by `string, char, escape_sequence { ... }`. The block is cloned
+    /// into one entry per selector so every expanded selector flows through the
+    /// existing `resolve` match arms unchanged; a multi-segment chain nests one
+    /// scope per segment.
+    fn insert_group(&mut self, selectors: Vec<Vec<SelectorSegment>>, block: Stylesheet) {
+        for chain in selectors {
+            self.insert_chain(&chain, block.clone());
+        }
+    }
+
+    /// Nest `block` under a descendant chain of selector segments, creating the
+    /// intermediate scopes as needed and placing the block at the leaf.
+    fn insert_chain(&mut self, chain: &[SelectorSegment], block: Stylesheet) {
+        match chain.split_first() {
+            None => self.style = self.style.merge_with(&block.style),
+            Some((segment, [])) => {
+                self.scopes.insert(segment.clone(), block);
+            }
+            Some((segment, rest)) => {
+                self.scopes.entry(segment.clone()).or_default().insert_chain(rest, block);
+            }
+        }
+    }
+
+    /// Descend the `Kind` scopes following `path`, returning the sub-stylesheet
+    /// it names if one exists.
+    fn lookup(&self, path: &[String]) -> Option<&Stylesheet> {
+        match path.split_first() {
+            None => Some(self),
+            Some((kind, rest)) => self
+                .scopes
+                .get(&SelectorSegment::Kind(kind.clone()))
+                .and_then(|stylesheet| stylesheet.lookup(rest)),
+        }
+    }
+
+    /// Fold every selector this block `@extend`s *under* `base`, resolved
+    /// against the root stylesheet, and return `base` merged on top. Extends
+    /// act as inherited defaults, so the extending block's own settings win
+    /// over the style it inherits — `merge_with`'s argument takes precedence,
+    /// matching how nested scopes override their parents. `visited` guards
+    /// against cycles by breaking any back-edge. Shared by the resolver and by
+    /// extended targets in turn.
+    fn extended_style(&self, base: StyleBuilder, root: &Stylesheet, visited: &mut Vec<SelectorPath>) -> StyleBuilder {
+        let extended = self.extends.iter().fold(StyleBuilder::default(), |style, selector| {
+            if visited.contains(selector) {
+                return style;
+            }
+            match root.lookup(selector) {
+                Some(target) => {
+                    visited.push(selector.clone());
+                    let extended = target.extended_style(target.style.clone(), root, visited);
+                    visited.pop();
+                    style.merge_with(&extended)
+                }
+                None => style,
+            }
+        });
+        extended.merge_with(&base)
+    }
 }
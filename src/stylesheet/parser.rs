@@ -0,0 +1,313 @@
+use std::path::PathBuf;
+
+use tree_sitter::{Node, Tree};
+
+use super::*;
+use crate::style::setting;
+
+/// Walks the tree-sitter parse tree of a `.syncat` stylesheet into a
+/// [`Stylesheet`] the resolver can consume.
+///
+/// The grammar (`tree-sitter-syncat-stylesheet`) names the nodes this walker
+/// dispatches on:
+///
+/// * `variable_definition` — a `$name: value;` binding,
+/// * `node` — a `selectors { ... }` rule,
+/// * `import` and `extend` — `@import "path";` and `@extend selector;`,
+/// * `selectors` / `selector` — a comma-separated list of descendant chains,
+/// * `style` — a `property: value;` declaration,
+/// * value nodes `color`, `string`, `number`, `boolean`, `variable` and
+///   `call` (a colour-transform function).
+///
+/// `$variable` references are substituted here, while a lexical scope stack is
+/// in hand, so every [`StyleBuilder`] stored in the returned tree already holds
+/// concrete settings and `resolve` never sees a variable.
+impl Stylesheet {
+    pub fn parse(source: &str, tree: Tree) -> crate::Result<Stylesheet> {
+        let mut stylesheet = Stylesheet::default();
+        let mut variables = Variables::default();
+        parse_block(&mut stylesheet, tree.root_node(), source, &mut variables)?;
+        Ok(stylesheet)
+    }
+}
+
+/// Fold every item of a block (the root, or the body of a `node`) into `sheet`.
+/// A fresh variable frame is pushed for the block so definitions inside it
+/// shadow outer bindings only for this block and its descendants.
+fn parse_block(sheet: &mut Stylesheet, node: Node, source: &str, variables: &mut Variables) -> crate::Result<()> {
+    variables.push();
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        match child.kind() {
+            "variable_definition" => {
+                let (name, value) = parse_variable_definition(child, source, variables)?;
+                variables.define(name, value);
+            }
+            "node" => parse_rule(sheet, child, source, variables)?,
+            "import" => {
+                // Record each imported path so the on-disk cache key folds in
+                // the sources of every file this stylesheet depends on.
+                if let Some(string) = child_of_kind(child, "string") {
+                    sheet.imports.push(PathBuf::from(unquote(node_text(string, source))));
+                }
+            }
+            "extend" => parse_extend(sheet, child, source)?,
+            "style" => parse_style(&mut sheet.style, child, source, variables)?,
+            _ => {}
+        }
+    }
+    variables.pop();
+    Ok(())
+}
+
+/// Parse a `selectors { ... }` rule, recursing into its body under a new
+/// sub-stylesheet keyed by the selector.
+fn parse_rule(parent: &mut Stylesheet, node: Node, source: &str, variables: &mut Variables) -> crate::Result<()> {
+    let selector = child_of_kind(node, "selectors")
+        .ok_or_else(|| Error::new("stylesheet rule is missing its selector".to_string()))?;
+    let body = child_of_kind(node, "node")
+        .or_else(|| child_of_kind(node, "block"))
+        .ok_or_else(|| Error::new("stylesheet rule is missing its body".to_string()))?;
+
+    let mut block = Stylesheet::default();
+    parse_block(&mut block, body, source, variables)?;
+    // A `string, char, escape_sequence { ... }` list expands into one scope
+    // entry per selector, all sharing the same parsed block.
+    parent.insert_group(parse_selector_list(selector, source)?, block);
+    Ok(())
+}
+
+/// Record an `@extend selectors;` edge on `sheet`. The selector names a chain
+/// of node kinds whose style this block inherits; `resolve` folds it in after
+/// the normal scopes.
+fn parse_extend(sheet: &mut Stylesheet, node: Node, source: &str) -> crate::Result<()> {
+    let selector = child_of_kind(node, "selectors")
+        .or_else(|| child_of_kind(node, "selector"))
+        .ok_or_else(|| Error::new("@extend is missing its selector".to_string()))?;
+    let path = node_text(selector, source)
+        .split_whitespace()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    sheet.extends.push(path);
+    Ok(())
+}
+
+fn parse_variable_definition(node: Node, source: &str, variables: &Variables) -> crate::Result<(String, Value)> {
+    let name = node_text(child_of_kind(node, "variable")
+        .ok_or_else(|| Error::new("variable definition is missing its name".to_string()))?, source)
+        .trim_start_matches('$')
+        .to_string();
+    let value = parse_value(value_node(node)?, source, variables)?;
+    Ok((name, value))
+}
+
+/// Apply a single `property: value;` declaration to `style`.
+fn parse_style(style: &mut StyleBuilder, node: Node, source: &str, variables: &Variables) -> crate::Result<()> {
+    let property = node_text(child_of_kind(node, "property")
+        .ok_or_else(|| Error::new("style declaration is missing its property".to_string()))?, source);
+    let value = parse_value(value_node(node)?, source, variables)?;
+    match (property, value) {
+        ("foreground", Value::Colour(colour)) => style.foreground = setting(false, colour),
+        ("background", Value::Colour(colour)) => style.background = setting(false, colour),
+        ("content", Value::Content(content)) => style.content = setting(false, content),
+        ("bold", Value::Boolean(on)) => style.bold = setting(false, on),
+        ("italic", Value::Boolean(on)) => style.italic = setting(false, on),
+        ("underline", Value::Boolean(on)) => style.underline = setting(false, on),
+        (other, _) => return Err(Error::new(format!("unknown or mistyped style property `{}`", other))),
+    }
+    Ok(())
+}
+
+/// Evaluate a value node to a concrete [`Value`], resolving any `$variable`
+/// reference against the scope stack.
+fn parse_value(node: Node, source: &str, variables: &Variables) -> crate::Result<Value> {
+    match node.kind() {
+        "variable" => {
+            let name = node_text(node, source).trim_start_matches('$');
+            variables
+                .get(name)
+                .cloned()
+                .ok_or_else(|| Error::new(format!("undefined variable `${}`", name)))
+        }
+        "string" => Ok(Value::Content(unquote(node_text(node, source)))),
+        "boolean" => match node_text(node, source) {
+            "true" => Ok(Value::Boolean(true)),
+            "false" => Ok(Value::Boolean(false)),
+            other => Err(Error::new(format!("`{}` is not a boolean", other))),
+        },
+        "call" => parse_call(node, source, variables),
+        _ => Ok(Value::Colour(parse_colour(node, source)?)),
+    }
+}
+
+/// Evaluate a colour-transform call — `lighten`, `darken`, `mix` or `alpha` —
+/// into a concrete RGB colour, resolving any `$variable` arguments first.
+fn parse_call(node: Node, source: &str, variables: &Variables) -> crate::Result<Value> {
+    let mut cursor = node.walk();
+    let children: Vec<Node> = node.named_children(&mut cursor).collect();
+    let (name, args) = children
+        .split_first()
+        .ok_or_else(|| Error::new("colour function is missing its name".to_string()))?;
+    let colour = match node_text(*name, source) {
+        "lighten" => super::color::lighten(&call_colour(args, 0, source, variables)?, call_percent(args, 1, source)?),
+        "darken" => super::color::darken(&call_colour(args, 0, source, variables)?, call_percent(args, 1, source)?),
+        "mix" => super::color::mix(
+            &call_colour(args, 0, source, variables)?,
+            &call_colour(args, 1, source, variables)?,
+            call_percent(args, 2, source)?,
+        ),
+        "alpha" => {
+            // A missing background argument falls back to blending against
+            // black, since the terminal cannot render true transparency.
+            let background = call_colour(args, 2, source, variables).unwrap_or(Colour::Black);
+            super::color::alpha(&call_colour(args, 0, source, variables)?, call_opacity(args, 1, source)?, &background)
+        }
+        other => return Err(Error::new(format!("unknown colour function `{}`", other))),
+    };
+    Ok(Value::Colour(colour))
+}
+
+fn call_colour(args: &[Node], index: usize, source: &str, variables: &Variables) -> crate::Result<Colour> {
+    let node = *args
+        .get(index)
+        .ok_or_else(|| Error::new("colour function is missing a colour argument".to_string()))?;
+    match parse_value(node, source, variables)? {
+        Value::Colour(colour) => Ok(colour),
+        Value::Content(..) | Value::Boolean(..) => Err(Error::new("expected a colour argument".to_string())),
+    }
+}
+
+/// Parse an `alpha` opacity argument into the 0–100 percentage `color::alpha`
+/// expects. An explicit `%` is taken as a percentage; a bare number is the
+/// conventional 0–1 alpha and is scaled up, so `alpha($c, 0.5)` and
+/// `alpha($c, 50%)` mean the same thing.
+fn call_opacity(args: &[Node], index: usize, source: &str) -> crate::Result<f32> {
+    let node = *args
+        .get(index)
+        .ok_or_else(|| Error::new("alpha is missing its opacity argument".to_string()))?;
+    let text = node_text(node, source).trim();
+    match text.strip_suffix('%') {
+        Some(percent) => percent.trim().parse::<f32>(),
+        None => text.parse::<f32>().map(|alpha| alpha * 100.0),
+    }
+    .map_err(|_| Error::new("expected an opacity (0–1 or a percentage)".to_string()))
+}
+
+fn call_percent(args: &[Node], index: usize, source: &str) -> crate::Result<f32> {
+    let node = *args
+        .get(index)
+        .ok_or_else(|| Error::new("colour function is missing a percentage argument".to_string()))?;
+    node_text(node, source)
+        .trim()
+        .trim_end_matches('%')
+        .trim()
+        .parse::<f32>()
+        .map_err(|_| Error::new("expected a percentage argument".to_string()))
+}
+
+/// Translate a (possibly comma-grouped) selector list into one descendant
+/// chain per selector. A bare `selectors` node with no `selector` children is
+/// treated as a single selector.
+fn parse_selector_list(node: Node, source: &str) -> crate::Result<Vec<Vec<SelectorSegment>>> {
+    let mut cursor = node.walk();
+    let chains: Vec<Vec<SelectorSegment>> = node
+        .named_children(&mut cursor)
+        .filter(|child| child.kind() == "selector")
+        .map(|child| parse_selector(child, source))
+        .collect::<crate::Result<_>>()?;
+    if chains.is_empty() {
+        Ok(vec![parse_selector(node, source)?])
+    } else {
+        Ok(chains)
+    }
+}
+
+/// Parse one selector into its descendant chain of [`SelectorSegment`]s, in
+/// source order, so `function_definition identifier` nests as two scopes.
+fn parse_selector(node: Node, source: &str) -> crate::Result<Vec<SelectorSegment>> {
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor)
+        .map(|segment| parse_segment(segment, source))
+        .collect()
+}
+
+/// Parse a single selector segment, mapping each grammar node onto the matching
+/// [`SelectorSegment`] variant.
+fn parse_segment(node: Node, source: &str) -> crate::Result<SelectorSegment> {
+    Ok(match node.kind() {
+        "token" | "string" => SelectorSegment::Token(unquote(node_text(node, source))),
+        "token_pattern" | "regex" => {
+            SelectorSegment::TokenPattern(node_text(node, source).trim_matches('/').to_string())
+        }
+        "direct_child" => SelectorSegment::DirectChild(Box::new(parse_inner_segment(node, source)?)),
+        "no_children" => SelectorSegment::NoChildren(Box::new(parse_inner_segment(node, source)?)),
+        "branch" => SelectorSegment::BranchCheck(parse_selector(node, source)?),
+        _ => SelectorSegment::Kind(node_text(node, source).to_string()),
+    })
+}
+
+/// Parse the single segment a `>` or `.` combinator wraps.
+fn parse_inner_segment(node: Node, source: &str) -> crate::Result<SelectorSegment> {
+    let inner = node
+        .named_child(0)
+        .ok_or_else(|| Error::new("selector combinator is missing its segment".to_string()))?;
+    parse_segment(inner, source)
+}
+
+fn parse_colour(node: Node, source: &str) -> crate::Result<Colour> {
+    let text = node_text(node, source);
+    match node.kind() {
+        "number" => text
+            .parse::<u8>()
+            .map(Colour::Fixed)
+            .map_err(|_| Error::new(format!("`{}` is not a valid 256-colour index", text))),
+        _ => named_colour(text)
+            .or_else(|| hex_colour(text))
+            .ok_or_else(|| Error::new(format!("`{}` is not a colour", text))),
+    }
+}
+
+fn named_colour(name: &str) -> Option<Colour> {
+    Some(match name {
+        "black" => Colour::Black,
+        "red" => Colour::Red,
+        "green" => Colour::Green,
+        "yellow" => Colour::Yellow,
+        "blue" => Colour::Blue,
+        "purple" => Colour::Purple,
+        "cyan" => Colour::Cyan,
+        "white" => Colour::White,
+        _ => return None,
+    })
+}
+
+fn hex_colour(text: &str) -> Option<Colour> {
+    let hex = text.strip_prefix('#')?;
+    if hex.len() < 6 {
+        return None;
+    }
+    let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
+    let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
+    let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
+    Some(Colour::RGB(r, g, b))
+}
+
+/// The value node of a definition or declaration: its last named child, after
+/// the name/property that precedes it.
+fn value_node(node: Node) -> crate::Result<Node> {
+    node.named_child(node.named_child_count().saturating_sub(1))
+        .ok_or_else(|| Error::new("declaration is missing its value".to_string()))
+}
+
+fn child_of_kind<'a>(node: Node<'a>, kind: &str) -> Option<Node<'a>> {
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor).find(|child| child.kind() == kind)
+}
+
+fn node_text<'a>(node: Node, source: &'a str) -> &'a str {
+    node.utf8_text(source.as_bytes()).unwrap_or_default()
+}
+
+fn unquote(text: &str) -> String {
+    text.trim_matches(|c| c == '"' || c == '\'').to_string()
+}
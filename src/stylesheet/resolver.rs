@@ -120,12 +120,16 @@ impl<'a> Context<'a> {
 
 impl Stylesheet {
     pub fn resolve(&self, context: &Context, scopes: &[(&str, usize)], token: Option<&str>) -> StyleBuilder {
-        self.scopes.iter()
+        self.resolve_with(self, context, scopes, token, &mut Vec::new())
+    }
+
+    fn resolve_with(&self, root: &Stylesheet, context: &Context, scopes: &[(&str, usize)], token: Option<&str>, visited: &mut Vec<SelectorPath>) -> StyleBuilder {
+        let style = self.scopes.iter()
             .fold(self.style.clone(), |style, (selector_segment, stylesheet)| match selector_segment {
                 SelectorSegment::Kind(name) => (0..scopes.len()).rev()
                     .fold(style, |style, i| {
                         if scopes[i].0 == name {
-                            style.merge_with(&stylesheet.resolve(context.child(i+1).unwrap_or(&Context::default()), &scopes[i+1..], token))
+                            style.merge_with(&stylesheet.resolve_with(root, context.child(i+1).unwrap_or(&Context::default()), &scopes[i+1..], token, visited))
                         } else {
                             style
                         }
@@ -146,7 +150,7 @@ impl Stylesheet {
                 }
                 SelectorSegment::BranchCheck(selector) => {
                     if context.satisfies_selector(&selector) {
-                        style.merge_with(&stylesheet.resolve(context, scopes, token))
+                        style.merge_with(&stylesheet.resolve_with(root, context, scopes, token, visited))
                     } else {
                         style
                     }
@@ -168,7 +172,7 @@ impl Stylesheet {
                 SelectorSegment::DirectChild(segment) => match segment.as_ref() {
                     SelectorSegment::Kind(name) => {
                         if scopes.first().map(|x| x.0) == Some(name) {
-                            style.merge_with(&stylesheet.resolve(context.child(1).unwrap_or(&Context::default()), &scopes[1..], token))
+                            style.merge_with(&stylesheet.resolve_with(root, context.child(1).unwrap_or(&Context::default()), &scopes[1..], token, visited))
                         } else {
                             style
                         }
@@ -204,6 +208,11 @@ impl Stylesheet {
                     SelectorSegment::BranchCheck(..) => unimplemented!("Consider using `[> selector]` instead of `> [selector]` for the same effect"),
                     SelectorSegment::DirectChild(..) => unreachable!(),
                 }
-            })
+            });
+
+        // Having folded the normal scopes, fold in every selector this block
+        // `@extend`s beneath them, so the folded style wins over what it
+        // inherits, reusing the same cycle-guarded walk the extended targets use.
+        self.extended_style(style, root, visited)
     }
 }
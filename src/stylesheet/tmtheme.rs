@@ -0,0 +1,196 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use super::{SelectorSegment, Stylesheet};
+use crate::error::Error;
+use crate::style::{setting, Colour, StyleBuilder};
+
+/// Maps a TextMate scope prefix onto the tree-sitter node kind that syncat
+/// selects on. The longest matching prefix wins; anything unmatched falls back
+/// to a `TokenPattern` that matches the raw token text.
+const SCOPE_KINDS: &[(&str, &str)] = &[
+    ("entity.name.function", "function_definition"),
+    ("entity.name.type", "type_identifier"),
+    ("entity.name.tag", "tag_name"),
+    ("keyword.control", "keyword"),
+    ("keyword.operator", "operator"),
+    ("keyword", "keyword"),
+    ("storage.type", "type_identifier"),
+    ("storage.modifier", "storage_specifier"),
+    ("string", "string"),
+    ("constant.character.escape", "escape_sequence"),
+    ("constant.numeric", "number"),
+    ("constant", "constant"),
+    ("comment", "comment"),
+    ("variable.parameter", "parameter"),
+    ("variable", "identifier"),
+    ("support.function", "function_definition"),
+];
+
+/// The raw shape of a `.tmTheme` (or syntect) theme: an ordered list of scope
+/// settings, deserialized straight from the plist.
+#[derive(Deserialize)]
+struct RawTheme {
+    #[serde(default)]
+    settings: Vec<RawRule>,
+}
+
+#[derive(Deserialize)]
+struct RawRule {
+    scope: Option<String>,
+    settings: RawStyle,
+}
+
+#[derive(Deserialize)]
+struct RawStyle {
+    foreground: Option<String>,
+    background: Option<String>,
+    #[serde(rename = "fontStyle")]
+    font_style: Option<String>,
+}
+
+impl Stylesheet {
+    /// Load a TextMate/syntect theme from disk and convert it to the
+    /// `Stylesheet` that `Stylesheet::parse` would have produced, ready for
+    /// `resolve`.
+    pub fn from_tm_theme(path: impl AsRef<Path>) -> crate::Result<Stylesheet> {
+        let theme: RawTheme = plist::from_file(path).map_err(|err| Error::new(err.to_string()))?;
+        Ok(convert(&theme))
+    }
+
+    /// Serialize this stylesheet back out as `.syncat` source so an imported
+    /// theme can be saved and hand-edited.
+    pub fn to_syncat(&self) -> String {
+        let mut out = String::new();
+        write_block(&mut out, self, 0);
+        out
+    }
+}
+
+fn convert(theme: &RawTheme) -> Stylesheet {
+    let mut stylesheet = Stylesheet::default();
+    for rule in &theme.settings {
+        let style = build_style(&rule.settings);
+        match &rule.scope {
+            // The global settings block (no scope) styles the document root.
+            None => stylesheet.style = stylesheet.style.merge_with(&style),
+            Some(scopes) => {
+                for scope in scopes.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+                    insert_chain(&mut stylesheet, &selector_chain(scope), style.clone());
+                }
+            }
+        }
+    }
+    stylesheet
+}
+
+/// Translate one TextMate scope into the selector chain syncat uses: a single
+/// node kind, taken from the longest prefix of the whole scope listed in
+/// [`SCOPE_KINDS`], or a `TokenPattern` on the whole scope when nothing
+/// matches. Space-separated descendant scopes are not split into a chain.
+fn selector_chain(scope: &str) -> Vec<SelectorSegment> {
+    match SCOPE_KINDS
+        .iter()
+        .filter(|(prefix, _)| scope.starts_with(prefix))
+        .max_by_key(|(prefix, _)| prefix.len())
+    {
+        Some((_, kind)) => vec![SelectorSegment::Kind(kind.to_string())],
+        None => vec![SelectorSegment::TokenPattern(format!("^{}$", regex::escape(scope)))],
+    }
+}
+
+fn insert_chain(stylesheet: &mut Stylesheet, chain: &[SelectorSegment], style: StyleBuilder) {
+    match chain.split_first() {
+        None => stylesheet.style = stylesheet.style.merge_with(&style),
+        Some((head, rest)) => {
+            let child = stylesheet.scopes.entry(head.clone()).or_default();
+            insert_chain(child, rest, style);
+        }
+    }
+}
+
+fn build_style(style: &RawStyle) -> StyleBuilder {
+    let mut builder = StyleBuilder::default();
+    if let Some(colour) = style.foreground.as_deref().and_then(parse_colour) {
+        builder.foreground = setting(false, colour);
+    }
+    if let Some(colour) = style.background.as_deref().and_then(parse_colour) {
+        builder.background = setting(false, colour);
+    }
+    if let Some(font_style) = &style.font_style {
+        for word in font_style.split_whitespace() {
+            match word {
+                "bold" => builder.bold = setting(false, true),
+                "italic" => builder.italic = setting(false, true),
+                "underline" => builder.underline = setting(false, true),
+                _ => {}
+            }
+        }
+    }
+    builder
+}
+
+/// Parse a `#rrggbb` (or `#rrggbbaa`) hex colour; the alpha channel is dropped
+/// since the terminal cannot render it.
+fn parse_colour(value: &str) -> Option<Colour> {
+    let hex = value.strip_prefix('#')?;
+    if hex.len() < 6 {
+        return None;
+    }
+    let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
+    let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
+    let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
+    Some(Colour::RGB(r, g, b))
+}
+
+fn write_block(out: &mut String, stylesheet: &Stylesheet, depth: usize) {
+    let indent = "    ".repeat(depth);
+    write_properties(out, &stylesheet.style, &indent);
+    for (selector, child) in &stylesheet.scopes {
+        let _ = writeln!(out, "{}{} {{", indent, selector_source(selector));
+        write_block(out, child, depth + 1);
+        let _ = writeln!(out, "{}}}", indent);
+    }
+}
+
+fn write_properties(out: &mut String, style: &StyleBuilder, indent: &str) {
+    if let Some(colour) = style.foreground() {
+        let _ = writeln!(out, "{}foreground: {};", indent, colour_source(colour));
+    }
+    if let Some(colour) = style.background() {
+        let _ = writeln!(out, "{}background: {};", indent, colour_source(colour));
+    }
+    if style.bold() == Some(true) {
+        let _ = writeln!(out, "{}bold: true;", indent);
+    }
+    if style.italic() == Some(true) {
+        let _ = writeln!(out, "{}italic: true;", indent);
+    }
+    if style.underline() == Some(true) {
+        let _ = writeln!(out, "{}underline: true;", indent);
+    }
+}
+
+fn selector_source(selector: &SelectorSegment) -> String {
+    match selector {
+        SelectorSegment::Kind(name) => name.clone(),
+        SelectorSegment::Token(token) => format!("{:?}", token),
+        SelectorSegment::TokenPattern(pattern) => format!("/{}/", pattern),
+        SelectorSegment::NoChildren(inner) => format!("{}.", selector_source(inner)),
+        SelectorSegment::DirectChild(inner) => format!("> {}", selector_source(inner)),
+        SelectorSegment::BranchCheck(inner) => {
+            let parts: Vec<_> = inner.iter().map(selector_source).collect();
+            format!("[{}]", parts.join(" "))
+        }
+    }
+}
+
+fn colour_source(colour: &Colour) -> String {
+    match colour {
+        Colour::RGB(r, g, b) => format!("#{:02x}{:02x}{:02x}", r, g, b),
+        Colour::Fixed(index) => index.to_string(),
+        other => format!("{:?}", other).to_lowercase(),
+    }
+}
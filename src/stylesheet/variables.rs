@@ -0,0 +1,45 @@
+use std::collections::BTreeMap;
+
+use crate::style::Colour;
+
+/// A value that a `$variable` can hold. Variables may stand in for any of the
+/// concrete settings a rule can take, so a value is a colour (for
+/// `foreground`/`background`), a string (for `content`), or a boolean (for the
+/// `bold`/`italic`/`underline` font styles).
+#[derive(Clone, Debug)]
+pub(crate) enum Value {
+    Colour(Colour),
+    Content(String),
+    Boolean(bool),
+}
+
+/// A lexically scoped set of `$variable` bindings, threaded through parsing.
+///
+/// Each block pushes a fresh frame; a definition in an inner frame shadows an
+/// outer one for that block and its descendants. Resolution walks frames from
+/// innermost to outermost so the nearest binding wins.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Variables {
+    frames: Vec<BTreeMap<String, Value>>,
+}
+
+impl Variables {
+    pub(crate) fn push(&mut self) {
+        self.frames.push(BTreeMap::new());
+    }
+
+    pub(crate) fn pop(&mut self) {
+        self.frames.pop();
+    }
+
+    pub(crate) fn define(&mut self, name: impl Into<String>, value: Value) {
+        if self.frames.is_empty() {
+            self.frames.push(BTreeMap::new());
+        }
+        self.frames.last_mut().unwrap().insert(name.into(), value);
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Option<&Value> {
+        self.frames.iter().rev().find_map(|frame| frame.get(name))
+    }
+}
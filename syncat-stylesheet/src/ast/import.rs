@@ -1,11 +1,13 @@
 use std::path::{PathBuf, Path};
 use tree_sitter::TreeCursor;
 use enquote::unquote;
+use glob::glob;
 use super::helper::*;
 
 #[derive(Clone, Debug)]
 pub(crate) struct Import {
     pub(crate) path: PathBuf,
+    pub(crate) lang: Option<String>,
 }
 
 impl AsRef<Path> for Import {
@@ -14,11 +16,70 @@ impl AsRef<Path> for Import {
     }
 }
 
+impl Import {
+    /// Whether this import should take effect for `lang`. Unguarded imports
+    /// apply to every language; `@import "rust.syncat" for rust;` only applies
+    /// when the active language matches.
+    pub(crate) fn applies_to(&self, lang: &str) -> bool {
+        self.lang.as_deref().map_or(true, |guard| guard == lang)
+    }
+
+    /// Expand this import against `base` into the concrete files it names, in a
+    /// stable order. A glob pattern (`themes/*.syncat`) matches every file it
+    /// resolves to, sorted by path; a plain path yields itself joined to `base`.
+    pub(crate) fn expand(&self, base: &Path) -> Vec<Import> {
+        let joined = base.join(&self.path);
+        let pattern = joined.to_string_lossy();
+        if !pattern.contains(['*', '?', '[']) {
+            // Resolve against `base` just as the glob branch does, so every
+            // expanded import carries a path the loader can open directly.
+            return vec![Import { path: joined, lang: self.lang.clone() }];
+        }
+        let mut paths: Vec<PathBuf> = glob(&pattern)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .collect();
+        paths.sort();
+        paths
+            .into_iter()
+            .map(|path| Import { path, lang: self.lang.clone() })
+            .collect()
+    }
+
+    /// Resolve a declared import list into the ordered, de-duplicated set of
+    /// concrete files to load for `lang`, relative to `base`.
+    ///
+    /// Globs are expanded, `for <lang>` guards are applied, and any path
+    /// already in `visited` (by canonical form) is skipped so that a file
+    /// imported twice — or an import cycle — is applied only once. Declaration
+    /// order is preserved, so a later import merged with `merge_with` wins.
+    pub(crate) fn resolve(imports: &[Import], base: &Path, lang: &str, visited: &mut Vec<PathBuf>) -> Vec<PathBuf> {
+        let mut resolved = Vec::new();
+        for import in imports {
+            if !import.applies_to(lang) {
+                continue;
+            }
+            for expanded in import.expand(base) {
+                let canonical = expanded.path.canonicalize().unwrap_or_else(|_| expanded.path.clone());
+                if visited.contains(&canonical) {
+                    continue;
+                }
+                visited.push(canonical);
+                resolved.push(expanded.path);
+            }
+        }
+        resolved
+    }
+}
+
 impl FromSource for Import {
     fn from_source(tree: &mut TreeCursor, source: &[u8]) -> crate::Result<Self> {
         children!(tree, "import");
         let path = unquote(text!(tree, source, "string")?)?.into();
+        // An optional `for <lang>` guard follows the path.
+        let lang = text!(tree, source, "language").ok().map(str::to_string);
         tree.goto_parent();
-        Ok(Import { path })
+        Ok(Import { path, lang })
     }
 }